@@ -0,0 +1,245 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+#[cfg(not(target_arch = "wasm32"))]
+use moka::future::Cache;
+
+use crate::model::pg_response::PgResponse;
+
+/// Runs a SQL statement and returns the responses it produced. Implemented
+/// by whatever actually talks to Postgres; [`CachedExecutor`] wraps any
+/// `Executor` to add a result cache in front of it.
+#[async_trait]
+pub trait Executor {
+    type Error;
+
+    async fn execute(&self, sql: &str) -> Result<Vec<PgResponse>, Self::Error>;
+}
+
+/// Wraps an [`Executor`], optionally caching results by normalized SQL text
+/// so repeated identical queries skip the round-trip. Disabled by default;
+/// call [`Self::with_cache`] to opt in.
+///
+/// Native-only: `moka::future::Cache` relies on a background eviction
+/// thread pool that `wasm32-unknown-unknown` doesn't have.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct CachedExecutor<E> {
+    inner: E,
+    cache: Option<Cache<String, Vec<PgResponse>>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<E> CachedExecutor<E> {
+    pub fn new(inner: E) -> Self {
+        Self { inner, cache: None }
+    }
+
+    /// Enables the cache, bounded to `capacity` entries with a `ttl` per
+    /// entry.
+    pub fn with_cache(mut self, capacity: u64, ttl: Duration) -> Self {
+        self.cache = Some(
+            Cache::builder()
+                .max_capacity(capacity)
+                .time_to_live(ttl)
+                .build(),
+        );
+        self
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl<E> Executor for CachedExecutor<E>
+where
+    E: Executor + Send + Sync,
+    E::Error: Send,
+{
+    type Error = E::Error;
+
+    async fn execute(&self, sql: &str) -> Result<Vec<PgResponse>, Self::Error> {
+        let Some(cache) = &self.cache else {
+            return self.inner.execute(sql).await;
+        };
+
+        let key = normalize_sql(sql);
+
+        if let Some(cached) = cache.get(&key).await {
+            return Ok(cached);
+        }
+
+        let responses = self.inner.execute(sql).await?;
+
+        // Only cache the shape a single plain `SELECT` actually produces (or
+        // an empty response, which has nothing to go stale). Any
+        // other shape (a multi-statement batch mixing reads and writes,
+        // e.g. `[Table, CommandComplete, CommandComplete]` for `SELECT ...;
+        // UPDATE ...;`) can't be trusted to be read-only just because it
+        // contains a `Table`, so it invalidates the cache instead.
+        let cacheable = matches!(
+            responses.as_slice(),
+            [] | [PgResponse::Table(_), PgResponse::CommandComplete(_)]
+        );
+
+        if cacheable {
+            cache.insert(key, responses.clone()).await;
+        } else {
+            cache.invalidate_all();
+        }
+
+        Ok(responses)
+    }
+}
+
+/// Collapses insignificant whitespace so queries that differ only in
+/// formatting share a cache entry.
+#[cfg(not(target_arch = "wasm32"))]
+fn normalize_sql(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test {
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::model::pg_response::{Header, Row, Table};
+
+    /// Returns its queued responses in order, one per call, and counts how
+    /// many times `execute` actually ran.
+    struct MockExecutor {
+        responses: Mutex<VecDeque<Vec<PgResponse>>>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl MockExecutor {
+        fn new(calls: Arc<AtomicUsize>, responses: Vec<Vec<PgResponse>>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into()),
+                calls,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Executor for MockExecutor {
+        type Error = ();
+
+        async fn execute(&self, _sql: &str) -> Result<Vec<PgResponse>, Self::Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(self
+                .responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("no more queued responses"))
+        }
+    }
+
+    /// A `Table` followed by the `CommandComplete` a plain `SELECT` produces.
+    fn select_response() -> Vec<PgResponse> {
+        vec![
+            PgResponse::Table(Table::new(
+                Header::new(vec!["id".into()]),
+                vec![Row::new(vec!["1".into()])],
+            )),
+            PgResponse::CommandComplete(1),
+        ]
+    }
+
+    #[tokio::test]
+    async fn with_cache_not_called_means_no_caching() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let executor = CachedExecutor::new(MockExecutor::new(
+            calls.clone(),
+            vec![select_response(), select_response()],
+        ));
+
+        executor.execute("select 1").await.unwrap();
+        executor.execute("select 1").await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn identical_query_is_served_from_cache_without_reinvoking_inner() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let executor =
+            CachedExecutor::new(MockExecutor::new(calls.clone(), vec![select_response()]))
+                .with_cache(10, Duration::from_secs(60));
+
+        let first = executor.execute("select 1").await.unwrap();
+        let second = executor.execute("select 1").await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first, second);
+    }
+
+    #[tokio::test]
+    async fn a_write_clears_the_cache() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let executor = CachedExecutor::new(MockExecutor::new(
+            calls.clone(),
+            vec![
+                select_response(),                    // 1: populates the cache
+                vec![PgResponse::CommandComplete(1)], // 2: a write, should invalidate
+                select_response(),                    // 3: cache was cleared, must re-run
+            ],
+        ))
+        .with_cache(10, Duration::from_secs(60));
+
+        executor.execute("select * from t").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Cache hit: doesn't touch the inner executor or its response queue.
+        executor.execute("select * from t").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // A different statement that writes clears the whole cache, since a
+        // write's effect on any previously-cached SELECT can't be scoped by
+        // SQL text alone.
+        executor.execute("update t set x = 1").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        executor.execute("select * from t").await.unwrap();
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            3,
+            "the write should have evicted the earlier SELECT's cache entry"
+        );
+    }
+
+    #[tokio::test]
+    async fn mixed_read_write_batch_is_never_cached_or_skipped() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mixed_batch = vec![
+            PgResponse::Table(Table::new(
+                Header::new(vec!["id".into()]),
+                vec![Row::new(vec!["1".into()])],
+            )),
+            PgResponse::CommandComplete(1),
+            PgResponse::CommandComplete(1),
+        ];
+        let executor = CachedExecutor::new(MockExecutor::new(
+            calls.clone(),
+            vec![mixed_batch.clone(), mixed_batch],
+        ))
+        .with_cache(10, Duration::from_secs(60));
+
+        executor
+            .execute("select x from t; update t set x = x + 1;")
+            .await
+            .unwrap();
+        executor
+            .execute("select x from t; update t set x = x + 1;")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            2,
+            "a batch that isn't exactly [Table, CommandComplete] must never be served from cache"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use super::{format_delimited, Formatter};
+use crate::model::pg_response::PgResponse;
+
+const DELIMITER: char = ',';
+
+/// Renders responses as RFC 4180 comma-separated values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvFormatter;
+
+impl Formatter for CsvFormatter {
+    fn format(&self, responses: &[PgResponse]) -> String {
+        format_delimited(responses, DELIMITER)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::pg_response::{Header, Row, Table};
+
+    #[test]
+    fn quotes_values_containing_comma_quote_or_newline_per_rfc_4180() {
+        let table = Table::new(
+            Header::new(vec!["name".into(), "note".into()]),
+            vec![Row::new(vec![
+                "Smith, John".into(),
+                "said \"hi\"\nagain".into(),
+            ])],
+        );
+
+        let csv = CsvFormatter.format(&[PgResponse::Table(table)]);
+
+        assert_eq!(
+            csv,
+            "name,note\n\"Smith, John\",\"said \"\"hi\"\"\nagain\"\n\n"
+        );
+    }
+}
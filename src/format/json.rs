@@ -0,0 +1,95 @@
+use serde_json::{json, Value};
+
+use super::Formatter;
+use crate::model::pg_response::PgResponse;
+
+/// Renders responses as a JSON array with one element per [`PgResponse`]: a
+/// `Table` becomes an array of row objects keyed by [`Header::columns`],
+/// and a `CommandComplete` becomes `{"command_complete": n}`.
+///
+/// [`Header::columns`]: crate::model::pg_response::Header::columns
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {
+    fn format(&self, responses: &[PgResponse]) -> String {
+        let value: Vec<Value> = responses.iter().map(response_to_value).collect();
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    }
+}
+
+fn response_to_value(response: &PgResponse) -> Value {
+    match response {
+        PgResponse::Table(table) => {
+            let columns = table.header().columns();
+            let rows = table
+                .rows()
+                .iter()
+                .map(|row| {
+                    let entries = columns
+                        .iter()
+                        .cloned()
+                        .zip(row.values().iter().cloned().map(Value::String));
+                    Value::Object(entries.collect())
+                })
+                .collect();
+
+            Value::Array(rows)
+        }
+        PgResponse::CommandComplete(rows_affected) => json!({ "command_complete": rows_affected }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::pg_response::{Header, Row, Table};
+
+    #[test]
+    fn renders_a_table_as_an_array_of_row_objects_keyed_by_column_name() {
+        let table = Table::new(
+            Header::new(vec!["id".into(), "name".into()]),
+            vec![
+                Row::new(vec!["1".into(), "alice".into()]),
+                Row::new(vec!["2".into(), "bob".into()]),
+            ],
+        );
+
+        let value: Value =
+            serde_json::from_str(&JsonFormatter.format(&[PgResponse::Table(table)])).unwrap();
+
+        assert_eq!(
+            value,
+            json!([[
+                { "id": "1", "name": "alice" },
+                { "id": "2", "name": "bob" },
+            ]])
+        );
+    }
+
+    #[test]
+    fn renders_command_complete_as_a_tagged_object() {
+        let value: Value =
+            serde_json::from_str(&JsonFormatter.format(&[PgResponse::CommandComplete(5)])).unwrap();
+
+        assert_eq!(value, json!([{ "command_complete": 5 }]));
+    }
+
+    #[test]
+    fn renders_mixed_responses_in_order() {
+        let table = Table::new(
+            Header::new(vec!["ok".into()]),
+            vec![Row::new(vec!["true".into()])],
+        );
+
+        let value: Value = serde_json::from_str(
+            &JsonFormatter.format(&[PgResponse::Table(table), PgResponse::CommandComplete(1)]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            value,
+            json!([[{ "ok": "true" }], { "command_complete": 1 }])
+        );
+    }
+}
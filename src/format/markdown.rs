@@ -0,0 +1,108 @@
+use super::Formatter;
+use crate::model::pg_response::PgResponse;
+
+/// Renders responses as GitHub-flavored Markdown: one pipe table per
+/// `Table`, and `CommandComplete` as an italicized summary line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownFormatter;
+
+impl Formatter for MarkdownFormatter {
+    fn format(&self, responses: &[PgResponse]) -> String {
+        let mut out = String::new();
+
+        for response in responses {
+            match response {
+                PgResponse::Table(table) => {
+                    let columns = table.header().columns();
+
+                    out.push_str("| ");
+                    out.push_str(
+                        &columns
+                            .iter()
+                            .map(|c| escape_cell(c))
+                            .collect::<Vec<_>>()
+                            .join(" | "),
+                    );
+                    out.push_str(" |\n|");
+                    out.push_str(&" --- |".repeat(columns.len()));
+                    out.push('\n');
+
+                    for row in table.rows() {
+                        out.push_str("| ");
+                        out.push_str(
+                            &row.values()
+                                .iter()
+                                .map(|v| escape_cell(v))
+                                .collect::<Vec<_>>()
+                                .join(" | "),
+                        );
+                        out.push_str(" |\n");
+                    }
+                }
+                PgResponse::CommandComplete(rows_affected) => {
+                    out.push_str(&format!("_{rows_affected} rows affected_\n"))
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// Escapes a pipe character, which would otherwise be parsed as a cell
+/// boundary in a Markdown table, and replaces literal newlines with `<br>`,
+/// since a raw `\n`/`\r` would split the cell across table rows and corrupt
+/// the rendering.
+fn escape_cell(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::pg_response::{Header, Row, Table};
+
+    #[test]
+    fn renders_a_pipe_table_with_escaped_cell_content() {
+        let table = Table::new(
+            Header::new(vec!["id".into(), "note".into()]),
+            vec![Row::new(vec!["1".into(), "a | b".into()])],
+        );
+
+        let markdown = MarkdownFormatter.format(&[PgResponse::Table(table)]);
+
+        assert_eq!(
+            markdown,
+            "| id | note |\n| --- | --- |\n| 1 | a \\| b |\n\n"
+        );
+    }
+
+    #[test]
+    fn renders_newlines_in_cell_content_as_br_instead_of_splitting_the_row() {
+        let table = Table::new(
+            Header::new(vec!["id".into(), "note".into()]),
+            vec![Row::new(vec![
+                "1".into(),
+                "line one\nline two\r\nline three".into(),
+            ])],
+        );
+
+        let markdown = MarkdownFormatter.format(&[PgResponse::Table(table)]);
+
+        assert_eq!(
+            markdown,
+            "| id | note |\n| --- | --- |\n| 1 | line one<br>line two<br>line three |\n\n"
+        );
+    }
+
+    #[test]
+    fn renders_command_complete_as_an_italic_summary() {
+        let markdown = MarkdownFormatter.format(&[PgResponse::CommandComplete(3)]);
+
+        assert_eq!(markdown, "_3 rows affected_\n\n");
+    }
+}
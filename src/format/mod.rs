@@ -0,0 +1,74 @@
+mod csv;
+mod json;
+mod markdown;
+#[cfg(not(target_arch = "wasm32"))]
+mod pretty;
+mod tsv;
+
+pub use csv::CsvFormatter;
+pub use json::JsonFormatter;
+pub use markdown::MarkdownFormatter;
+#[cfg(not(target_arch = "wasm32"))]
+pub use pretty::PrettyFormatter;
+pub use tsv::TsvFormatter;
+
+use crate::model::pg_response::PgResponse;
+
+/// Renders a batch of [`PgResponse`]s produced by a single query run into a
+/// single output string, so the same results can be piped into other tools
+/// instead of only being printed to the terminal.
+pub trait Formatter {
+    fn format(&self, responses: &[PgResponse]) -> String;
+}
+
+/// Escapes a single cell for a delimiter-separated format per RFC 4180:
+/// values containing the delimiter, a double quote or a newline are wrapped
+/// in double quotes, with embedded quotes doubled.
+pub(crate) fn escape_delimited(value: &str, delimiter: char) -> String {
+    let needs_quoting = value.contains(delimiter)
+        || value.contains('"')
+        || value.contains('\n')
+        || value.contains('\r');
+
+    if !needs_quoting {
+        return value.to_string();
+    }
+
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// Shared body for [`CsvFormatter`] and [`TsvFormatter`]: one line of
+/// delimited values per header/row, a `COMMAND COMPLETE` line per
+/// `CommandComplete`, and a blank line after each response (the last one
+/// included).
+pub(crate) fn format_delimited(responses: &[PgResponse], delimiter: char) -> String {
+    let mut out = String::new();
+
+    let render_line = |values: &[String]| -> String {
+        values
+            .iter()
+            .map(|value| escape_delimited(value, delimiter))
+            .collect::<Vec<_>>()
+            .join(&delimiter.to_string())
+    };
+
+    for response in responses {
+        match response {
+            PgResponse::Table(table) => {
+                out.push_str(&render_line(table.header().columns()));
+                out.push('\n');
+
+                for row in table.rows() {
+                    out.push_str(&render_line(row.values()));
+                    out.push('\n');
+                }
+            }
+            PgResponse::CommandComplete(rows_affected) => {
+                out.push_str(&format!("COMMAND COMPLETE{delimiter}{rows_affected}\n"));
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
@@ -0,0 +1,36 @@
+use super::Formatter;
+use crate::model::pg_response::PgResponse;
+
+/// Renders responses for terminal display: each `Table` as a `prettytable`
+/// grid, each `CommandComplete` as a one-line summary.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrettyFormatter;
+
+impl Formatter for PrettyFormatter {
+    fn format(&self, responses: &[PgResponse]) -> String {
+        let mut out = String::new();
+
+        for response in responses {
+            match response {
+                PgResponse::Table(table) => out.push_str(&table.as_pretty().to_string()),
+                PgResponse::CommandComplete(rows_affected) => {
+                    out.push_str(&format!("COMMAND COMPLETE ({rows_affected} rows)\n"))
+                }
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn renders_command_complete_as_a_one_line_summary() {
+        let rendered = PrettyFormatter.format(&[PgResponse::CommandComplete(7)]);
+
+        assert_eq!(rendered, "COMMAND COMPLETE (7 rows)\n");
+    }
+}
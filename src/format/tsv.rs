@@ -0,0 +1,33 @@
+use super::{format_delimited, Formatter};
+use crate::model::pg_response::PgResponse;
+
+const DELIMITER: char = '\t';
+
+/// Renders responses as tab-separated values, quoted the same way
+/// [`super::CsvFormatter`] quotes commas.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TsvFormatter;
+
+impl Formatter for TsvFormatter {
+    fn format(&self, responses: &[PgResponse]) -> String {
+        format_delimited(responses, DELIMITER)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::pg_response::{Header, Row, Table};
+
+    #[test]
+    fn quotes_values_containing_a_tab() {
+        let table = Table::new(
+            Header::new(vec!["name".into()]),
+            vec![Row::new(vec!["a\tb".into()])],
+        );
+
+        let tsv = TsvFormatter.format(&[PgResponse::Table(table)]);
+
+        assert_eq!(tsv, "name\n\"a\tb\"\n\n");
+    }
+}
@@ -0,0 +1,37 @@
+//! ## `wasm32-unknown-unknown`: NOT YET SUPPORTED (source-side prep only, request open)
+//!
+//! This crate's batch-processing and formatting code (`model`, `format`) is
+//! meant to run in a browser client as well as the CLI. That's NOT true yet:
+//! this crate does not compile for `wasm32-unknown-unknown` today, and this
+//! doc section only tracks source-side prep, not delivered support. The
+//! request this tracks (wasm32 target support) stays OPEN until the
+//! manifest-side work below lands and `cargo build --target
+//! wasm32-unknown-unknown` actually passes — don't treat the tagged commits
+//! here as having closed it.
+//!
+//! Reaching it needs manifest-side changes this tree can't make, since there
+//! is no `Cargo.toml` here: add a `wasm` feature, point `tokio-postgres` at
+//! its `js` feature instead of native sockets, and make `moka`/`async-stream`
+//! build for the target (or drop them behind the same feature). Treat actual
+//! wasm32 support as blocked on that manifest work landing, not as shipped.
+//! What's actually done on the source side so far:
+//!
+//! - `Table::as_pretty` and the `PrettyFormatter` it backs are gated out on
+//!   `wasm32-unknown-unknown` (see
+//!   [`model::pg_response::Table::as_pretty`]): `prettytable` draws to a
+//!   real terminal, which a wasm build has none of. Render through one of
+//!   the other [`format::Formatter`] impls instead, e.g. `MarkdownFormatter`.
+//! - [`executor::CachedExecutor`] is gated out the same way: `moka`'s
+//!   `future::Cache` relies on a native background eviction thread pool
+//!   that isn't available on `wasm32-unknown-unknown`.
+//!
+//! `PgResponse::process_batches`/`from_typed`/`process_stream` are left
+//! unconditional — they only touch `tokio-postgres`'s protocol types
+//! (`Row`, `Statement`, `SimpleQueryMessage`, `Type`), which are the same
+//! regardless of the `js` vs. native socket feature, so they need no
+//! gating here; they do still need the manifest-side feature switch this
+//! tree doesn't have in order to actually build for the target.
+
+pub mod executor;
+pub mod format;
+pub mod model;
@@ -0,0 +1 @@
+pub mod pg_response;
@@ -1,13 +1,31 @@
-use tokio_postgres::SimpleQueryMessage;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use futures_util::{Stream, StreamExt};
+use rust_decimal::Decimal;
+use tokio_postgres::types::{FromSql, Kind, Type};
+use tokio_postgres::{Row as PgRow, SimpleQueryMessage, SimpleQueryRow, Statement};
+use uuid::Uuid;
+
+/// Placeholder rendered for a cell whose value is SQL NULL.
+pub const NULL_SENTINEL: &str = "[null]";
+
+/// Placeholder rendered for a cell whose value is a real, non-NULL value
+/// that failed to decode as its declared Postgres type (e.g. the wire
+/// format didn't match what `FromSql` expected). Kept distinct from
+/// [`NULL_SENTINEL`] so a decode failure is never mistaken for NULL.
+pub const DECODE_ERROR_SENTINEL: &str = "[decode error]";
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Header {
     columns: Vec<String>,
+    types: Vec<Type>,
 }
 
 impl Header {
     pub fn new(columns: Vec<String>) -> Self {
-        Self { columns }
+        Self {
+            columns,
+            types: vec![],
+        }
     }
 
     pub fn push<T: Into<String>>(&mut self, col: T) {
@@ -18,6 +36,26 @@ impl Header {
     pub fn set_columns(&mut self, columns: Vec<String>) {
         self.columns = columns;
     }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Set the Postgres type of each column, in the same order as
+    /// `columns`, so renderers can align and style cells per type.
+    ///
+    /// Only [`PgResponse::from_typed`] populates this: the simple query
+    /// protocol `PgResponse::process_batches` reads from doesn't send
+    /// column types, so `SimpleQueryColumn` has no `type_()` to read and
+    /// `process_batches`-built tables always leave this empty, which makes
+    /// `as_pretty` fall back to left-aligned, unstyled cells for them.
+    pub fn set_types(&mut self, types: Vec<Type>) {
+        self.types = types;
+    }
+
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -33,29 +71,127 @@ impl Row {
     pub fn push<T: Into<String>>(&mut self, value: T) {
         self.values.push(value.into());
     }
+
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Table {
     header: Header,
     rows: Vec<Row>,
+    null_sentinel: String,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self {
+            header: Header::default(),
+            rows: vec![],
+            null_sentinel: NULL_SENTINEL.to_string(),
+        }
+    }
 }
 
 impl Table {
     pub fn new(header: Header, rows: Vec<Row>) -> Self {
-        Self { header, rows }
+        Self {
+            header,
+            rows,
+            null_sentinel: NULL_SENTINEL.to_string(),
+        }
+    }
+
+    /// Use a different placeholder than [`NULL_SENTINEL`] when rendering a
+    /// NULL cell. Rewrites any cell already holding the current sentinel to
+    /// the new one, so the replacement takes effect both in the stored cell
+    /// text and in `as_pretty`'s dim-styling check.
+    ///
+    /// NULL-ness is tracked by string equality against the sentinel, so a
+    /// real text cell whose value happens to equal it is indistinguishable
+    /// from a genuine NULL and gets rewritten too.
+    pub fn with_null_sentinel<T: Into<String>>(mut self, null_sentinel: T) -> Self {
+        let null_sentinel = null_sentinel.into();
+
+        for row in &mut self.rows {
+            for value in &mut row.values {
+                if *value == self.null_sentinel {
+                    *value = null_sentinel.clone();
+                }
+            }
+        }
+
+        self.null_sentinel = null_sentinel;
+        self
     }
 
+    /// Renders this table as a `prettytable` grid for terminal display.
+    /// Native-only: `prettytable` draws box-drawing characters to a real
+    /// terminal, which is meaningless in a `wasm32` browser client — use
+    /// [`crate::format::Formatter`] impls like `MarkdownFormatter` there
+    /// instead.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn as_pretty(&self) -> prettytable::Table {
+        use prettytable::format::Alignment;
+        use prettytable::{Attr, Cell, Row as PrettyRow};
+
         let mut table = prettytable::Table::new();
 
         table.add_row(self.header.columns.clone().into());
-        self.rows.iter().map(|row| &row.values).for_each(|row| {
-            table.add_row(row.into());
-        });
+
+        for row in &self.rows {
+            let cells = row
+                .values
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| {
+                    let alignment = self
+                        .header
+                        .types
+                        .get(idx)
+                        .map(alignment_for_type)
+                        .unwrap_or(Alignment::LEFT);
+
+                    let mut cell = Cell::new_align(value, alignment);
+
+                    if value == &self.null_sentinel {
+                        cell = cell.with_style(Attr::Dim);
+                    }
+
+                    cell
+                })
+                .collect::<Vec<_>>();
+
+            table.add_row(PrettyRow::new(cells));
+        }
 
         table
     }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+}
+
+/// Picks the column alignment that matches how a SQL shell would render
+/// this Postgres type: numbers right-aligned, booleans centered, everything
+/// else (including text and types we don't special-case) left-aligned.
+#[cfg(not(target_arch = "wasm32"))]
+fn alignment_for_type(ty: &Type) -> prettytable::format::Alignment {
+    use prettytable::format::Alignment;
+
+    match *ty {
+        Type::INT2 | Type::INT4 | Type::INT8 | Type::FLOAT4 | Type::FLOAT8 | Type::NUMERIC => {
+            Alignment::RIGHT
+        }
+        Type::BOOL => Alignment::CENTER,
+        _ => Alignment::LEFT,
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -65,6 +201,10 @@ pub enum PgResponse {
 }
 
 impl PgResponse {
+    /// Builds `Table`s from simple-query protocol messages. `SimpleQueryRow`
+    /// carries column names but not types, so the resulting tables leave
+    /// `Header::types` empty; use [`Self::from_typed`] when type-aware
+    /// alignment/styling matters.
     pub fn process_batches(batches: Vec<SimpleQueryMessage>) -> Vec<PgResponse> {
         let mut responses = vec![];
 
@@ -73,45 +213,12 @@ impl PgResponse {
         for batch in batches {
             match batch {
                 SimpleQueryMessage::Row(row) => {
-                    let current_table = table.get_or_insert_with(|| {
-                        let mut table = Table::default();
-                        let column_names = row
-                            .columns()
-                            .iter()
-                            .map(|col| col.name().to_string())
-                            .collect::<Vec<String>>();
-
-                        prev_columns = column_names.clone();
-                        table.header.columns = column_names;
-
-                        table
-                    });
-
-                    let current_columns = row
-                        .columns()
-                        .iter()
-                        .map(|c| c.name().to_string())
-                        .collect::<Vec<_>>();
-
-                    let row_values = (0..current_columns.len())
-                        .map(|idx| row.get(idx).unwrap_or("[null]").to_string())
-                        .collect::<Vec<_>>();
-
-                    if current_columns == prev_columns {
-                        current_table.rows.push(Row::new(row_values));
-                    } else {
-                        let ready_table = table.take().unwrap();
+                    if let Some(ready_table) = push_row(&mut table, &mut prev_columns, row) {
                         responses.push(PgResponse::Table(ready_table));
-
-                        let header = Header::new(current_columns.clone());
-                        table = Some(Table::new(header, vec![Row::new(row_values)]));
-                        prev_columns = current_columns;
                     }
                 }
                 SimpleQueryMessage::CommandComplete(rows_affected) => {
-                    if table.is_some() {
-                        let ready_table = table.take().unwrap();
-                        prev_columns = vec![];
+                    if let Some(ready_table) = take_pending_table(&mut table, &mut prev_columns) {
                         responses.push(PgResponse::Table(ready_table));
                     }
                     responses.push(PgResponse::CommandComplete(rows_affected))
@@ -126,6 +233,270 @@ impl PgResponse {
 
         responses
     }
+
+    /// Like [`Self::process_batches`], but consumes a `Stream` of
+    /// `SimpleQueryMessage`s and yields a `Table` as soon as it reaches
+    /// `chunk_rows` rows (or sooner, on a column change or
+    /// `CommandComplete`), so a large result set can be rendered
+    /// incrementally instead of being buffered in full before anything is
+    /// returned. The column-change detection from `process_batches` is
+    /// preserved across chunk boundaries: `prev_columns` only resets when
+    /// the result set itself changes shape, not when a chunk is flushed
+    /// because it hit the high-water mark.
+    pub fn process_stream(
+        batches: impl Stream<Item = SimpleQueryMessage> + Unpin,
+        chunk_rows: usize,
+    ) -> impl Stream<Item = PgResponse> {
+        async_stream::stream! {
+            let mut batches = batches;
+            let mut table: Option<Table> = None;
+            let mut prev_columns: Vec<String> = vec![];
+
+            while let Some(batch) = batches.next().await {
+                match batch {
+                    SimpleQueryMessage::Row(row) => {
+                        if let Some(ready_table) = push_row(&mut table, &mut prev_columns, row) {
+                            yield PgResponse::Table(ready_table);
+                        } else if table.as_ref().is_some_and(|t| t.rows.len() >= chunk_rows) {
+                            yield PgResponse::Table(table.take().unwrap());
+                        }
+                    }
+                    SimpleQueryMessage::CommandComplete(rows_affected) => {
+                        if let Some(ready_table) = take_pending_table(&mut table, &mut prev_columns) {
+                            yield PgResponse::Table(ready_table);
+                        }
+                        yield PgResponse::CommandComplete(rows_affected);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            if let Some(table) = table {
+                yield PgResponse::Table(table);
+            }
+        }
+    }
+
+    /// Builds a single `Table` from the rows of a prepared statement,
+    /// decoding each cell through its real Postgres type (via `FromSql`)
+    /// instead of the pre-stringified representation `SimpleQuery` returns.
+    /// Returns an empty `Vec` (no `Table`) when `rows` is empty.
+    ///
+    /// No `HashMap<Oid, Type>` is needed to resolve composite/array OIDs:
+    /// `col.type_()` on a prepared `Statement` is already the fully resolved
+    /// `Type`, including the element `Type` for array columns via
+    /// `Kind::Array`.
+    pub fn from_typed(statement: &Statement, rows: &[PgRow]) -> Vec<PgResponse> {
+        if rows.is_empty() {
+            return vec![];
+        }
+
+        let columns = statement
+            .columns()
+            .iter()
+            .map(|col| col.name().to_string())
+            .collect::<Vec<_>>();
+        let types = statement
+            .columns()
+            .iter()
+            .map(|col| col.type_().clone())
+            .collect::<Vec<_>>();
+
+        let table_rows = rows
+            .iter()
+            .map(|row| {
+                let values = statement
+                    .columns()
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, col)| decode_cell(row, idx, col.type_()))
+                    .collect();
+
+                Row::new(values)
+            })
+            .collect();
+
+        let mut header = Header::new(columns);
+        header.set_types(types);
+
+        vec![PgResponse::Table(Table::new(header, table_rows))]
+    }
+}
+
+/// Feeds one `SimpleQueryRow` into the in-progress `table`, starting a new
+/// one on the first row or on a column-set change. Shared by
+/// [`PgResponse::process_batches`] and [`PgResponse::process_stream`] so the
+/// column-change detection only has to be gotten right in one place.
+///
+/// Returns the previous table once it's complete (the column set changed),
+/// so the caller can flush it; returns `None` while `table` is still
+/// accumulating rows for the same result set.
+fn push_row(
+    table: &mut Option<Table>,
+    prev_columns: &mut Vec<String>,
+    row: SimpleQueryRow,
+) -> Option<Table> {
+    let current_columns = row
+        .columns()
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect::<Vec<_>>();
+
+    let row_values = (0..current_columns.len())
+        .map(|idx| row.get(idx).unwrap_or(NULL_SENTINEL).to_string())
+        .collect::<Vec<_>>();
+
+    accumulate_row(table, prev_columns, current_columns, row_values)
+}
+
+/// The column-change/accumulation state machine behind [`push_row`], split
+/// out to take plain `Vec<String>` column names and row values instead of a
+/// `SimpleQueryRow`, which can only be built from a live wire response.
+fn accumulate_row(
+    table: &mut Option<Table>,
+    prev_columns: &mut Vec<String>,
+    current_columns: Vec<String>,
+    row_values: Vec<String>,
+) -> Option<Table> {
+    if table.is_none() {
+        *prev_columns = current_columns.clone();
+        *table = Some(Table::default());
+        table.as_mut().unwrap().header.columns = current_columns.clone();
+    }
+
+    if current_columns == *prev_columns {
+        table.as_mut().unwrap().rows.push(Row::new(row_values));
+        None
+    } else {
+        let ready_table = table.take().unwrap();
+
+        let header = Header::new(current_columns.clone());
+        *table = Some(Table::new(header, vec![Row::new(row_values)]));
+        *prev_columns = current_columns;
+
+        Some(ready_table)
+    }
+}
+
+/// Takes the in-progress table, if any, on a `CommandComplete` boundary,
+/// resetting `prev_columns` so the next result set starts a fresh table
+/// rather than being compared against the one that just ended.
+fn take_pending_table(table: &mut Option<Table>, prev_columns: &mut Vec<String>) -> Option<Table> {
+    if table.is_some() {
+        *prev_columns = vec![];
+    }
+    table.take()
+}
+
+/// Decodes a single cell into its display form using the matching `FromSql`
+/// impl for `ty`. Types we don't special-case fall back to [`RawText`],
+/// which accepts any wire format, so a column we haven't named here still
+/// renders its actual value instead of being swallowed into
+/// [`NULL_SENTINEL`]. Arrays of a type we do know how to decode are
+/// rendered element-by-element via [`decode_array_cell`], using the element
+/// `Type` `ty.kind()` already carries.
+fn decode_cell(row: &PgRow, idx: usize, ty: &Type) -> String {
+    match *ty {
+        Type::BOOL => render(row.try_get::<_, Option<bool>>(idx), |v| v.to_string()),
+        Type::INT2 => render(row.try_get::<_, Option<i16>>(idx), |v| v.to_string()),
+        Type::INT4 => render(row.try_get::<_, Option<i32>>(idx), |v| v.to_string()),
+        Type::INT8 => render(row.try_get::<_, Option<i64>>(idx), |v| v.to_string()),
+        Type::FLOAT4 => render(row.try_get::<_, Option<f32>>(idx), |v| v.to_string()),
+        Type::FLOAT8 => render(row.try_get::<_, Option<f64>>(idx), |v| v.to_string()),
+        Type::NUMERIC => render(row.try_get::<_, Option<Decimal>>(idx), |v| v.to_string()),
+        Type::UUID => render(row.try_get::<_, Option<Uuid>>(idx), |v| v.to_string()),
+        Type::DATE => render(row.try_get::<_, Option<NaiveDate>>(idx), |v| v.to_string()),
+        Type::TIME => render(row.try_get::<_, Option<NaiveTime>>(idx), |v| v.to_string()),
+        Type::TIMESTAMP => render(row.try_get::<_, Option<NaiveDateTime>>(idx), |v| {
+            v.to_string()
+        }),
+        Type::TIMESTAMPTZ => render(row.try_get::<_, Option<DateTime<Utc>>>(idx), |ts| {
+            ts.to_rfc3339()
+        }),
+        Type::JSON | Type::JSONB => render(row.try_get::<_, Option<serde_json::Value>>(idx), |v| {
+            v.to_string()
+        }),
+        Type::BYTEA => render(row.try_get::<_, Option<Vec<u8>>>(idx), |bytes| {
+            format!("\\x{}", hex_encode(&bytes))
+        }),
+        _ => match ty.kind() {
+            Kind::Array(element) => decode_array_cell(row, idx, element),
+            _ => render(row.try_get::<_, Option<RawText>>(idx), |v| v.0),
+        },
+    }
+}
+
+/// Decodes an array cell whose `element` type is one we know how to decode,
+/// rendering it as a Postgres array literal (`{1,NULL,3}`). Elements of a
+/// type we don't special-case here fall back to the same [`RawText`]
+/// handling [`decode_cell`] uses for an unmatched scalar.
+fn decode_array_cell(row: &PgRow, idx: usize, element: &Type) -> String {
+    fn fmt<T: ToString>(items: Vec<Option<T>>) -> String {
+        let rendered = items
+            .into_iter()
+            .map(|v| {
+                v.map(|v| v.to_string())
+                    .unwrap_or_else(|| "NULL".to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{{{rendered}}}")
+    }
+
+    match *element {
+        Type::BOOL => render(row.try_get::<_, Option<Vec<Option<bool>>>>(idx), fmt),
+        Type::INT2 => render(row.try_get::<_, Option<Vec<Option<i16>>>>(idx), fmt),
+        Type::INT4 => render(row.try_get::<_, Option<Vec<Option<i32>>>>(idx), fmt),
+        Type::INT8 => render(row.try_get::<_, Option<Vec<Option<i64>>>>(idx), fmt),
+        Type::FLOAT4 => render(row.try_get::<_, Option<Vec<Option<f32>>>>(idx), fmt),
+        Type::FLOAT8 => render(row.try_get::<_, Option<Vec<Option<f64>>>>(idx), fmt),
+        Type::TEXT | Type::VARCHAR => {
+            render(row.try_get::<_, Option<Vec<Option<String>>>>(idx), fmt)
+        }
+        Type::UUID => render(row.try_get::<_, Option<Vec<Option<Uuid>>>>(idx), fmt),
+        _ => render(row.try_get::<_, Option<RawText>>(idx), |v| v.0),
+    }
+}
+
+/// Turns a `try_get::<Option<T>>` result into its display form, keeping
+/// "column is SQL NULL" (`Ok(None)`) distinct from "value didn't decode as
+/// the declared type" (`Err`) — collapsing both to the same marker is
+/// exactly the ambiguity typed decoding is meant to remove.
+fn render<T, E>(result: Result<Option<T>, E>, to_string: impl FnOnce(T) -> String) -> String {
+    match result {
+        Ok(Some(value)) => to_string(value),
+        Ok(None) => NULL_SENTINEL.to_string(),
+        Err(_) => DECODE_ERROR_SENTINEL.to_string(),
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Decodes any Postgres wire value as either UTF-8 text or, failing that,
+/// hex-encoded bytes. Used as the fallback arm of [`decode_cell`] so a
+/// column of a type we don't special-case (composite, enum, array, ...)
+/// still renders its real value instead of erroring out and being mistaken
+/// for a genuine SQL NULL.
+struct RawText(String);
+
+impl<'a> FromSql<'a> for RawText {
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let text = match std::str::from_utf8(raw) {
+            Ok(text) => text.to_string(),
+            Err(_) => format!("\\x{}", hex_encode(raw)),
+        };
+
+        Ok(RawText(text))
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -134,15 +505,16 @@ mod test {
     use cascade::cascade;
 
     #[test]
+    #[cfg(not(target_arch = "wasm32"))]
     fn prettytable() {
-        let table = Table {
-            header: cascade! {
+        let table = Table::new(
+            cascade! {
                 Header::default();
                 ..push("col1");
                 ..push("col2");
                 ..push("col3");
             },
-            rows: cascade! {
+            cascade! {
                 vec![];
                 ..push(cascade! {
                     Row::default();
@@ -163,9 +535,244 @@ mod test {
                     ..push("value3_3");
                 });
             },
-        };
+        );
 
         let pretty = table.as_pretty();
         pretty.printstd();
     }
-}
\ No newline at end of file
+
+    // `decode_cell` takes a `tokio_postgres::Row`, which can only be built
+    // from a live wire response, so these exercise the `FromSql` round trip
+    // each decode arm relies on directly instead.
+
+    #[test]
+    fn numeric_and_uuid_decode_to_their_real_value_not_null_sentinel() {
+        use bytes::BytesMut;
+        use tokio_postgres::types::ToSql;
+
+        let decimal = Decimal::new(12345, 2);
+        let mut buf = BytesMut::new();
+        decimal.to_sql(&Type::NUMERIC, &mut buf).unwrap();
+        let decoded = Decimal::from_sql(&Type::NUMERIC, &buf).unwrap();
+        assert_eq!(decoded.to_string(), "123.45");
+        assert_ne!(decoded.to_string(), NULL_SENTINEL);
+
+        let uuid = Uuid::from_u128(0x1234_5678_9abc_def0_1234_5678_9abc_def0);
+        let mut buf = BytesMut::new();
+        uuid.to_sql(&Type::UUID, &mut buf).unwrap();
+        let decoded = Uuid::from_sql(&Type::UUID, &buf).unwrap();
+        assert_eq!(decoded, uuid);
+        assert_ne!(decoded.to_string(), NULL_SENTINEL);
+    }
+
+    #[test]
+    fn raw_text_fallback_decodes_unmatched_types_instead_of_erroring() {
+        let decoded = RawText::from_sql(&Type::INT4, b"hello").unwrap();
+        assert_eq!(decoded.0, "hello");
+
+        // Unlike the old `Option<String>` catch-all (which only `accepts`
+        // text-format columns), `RawText` accepts every type, so an
+        // unmatched column's real value is always rendered.
+        assert!(RawText::accepts(&Type::INT4));
+        assert!(RawText::accepts(&Type::JSON));
+    }
+
+    #[test]
+    fn render_keeps_decode_errors_distinct_from_genuine_null() {
+        assert_eq!(render(Ok::<_, ()>(Some(1)), |v: i32| v.to_string()), "1");
+        assert_eq!(
+            render(Ok::<_, ()>(None::<i32>), |v: i32| v.to_string()),
+            NULL_SENTINEL
+        );
+        assert_eq!(
+            render(Err(()), |v: i32| v.to_string()),
+            DECODE_ERROR_SENTINEL
+        );
+        assert_ne!(DECODE_ERROR_SENTINEL, NULL_SENTINEL);
+    }
+
+    // `process_batches`/`process_stream` both drive `accumulate_row`, but a
+    // `SimpleQueryRow` (like the `tokio_postgres::Row` `decode_cell` needs)
+    // can only be built from a live wire response, so these exercise the
+    // shared state machine directly with plain column names/values instead.
+    // `process_stream` itself is driven separately below, since unlike
+    // `SimpleQueryRow` a bare `SimpleQueryMessage::CommandComplete` needs no
+    // connection to construct.
+
+    #[tokio::test]
+    async fn process_stream_yields_command_complete_messages_in_order() {
+        use futures_util::stream;
+
+        let batches = stream::iter(vec![
+            SimpleQueryMessage::CommandComplete(1),
+            SimpleQueryMessage::CommandComplete(2),
+        ]);
+
+        let responses: Vec<PgResponse> = PgResponse::process_stream(batches, 2).collect().await;
+
+        assert_eq!(
+            responses,
+            vec![
+                PgResponse::CommandComplete(1),
+                PgResponse::CommandComplete(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn accumulate_row_merges_consecutive_rows_with_the_same_columns() {
+        let mut table: Option<Table> = None;
+        let mut prev_columns = vec![];
+        let columns = vec!["id".to_string()];
+
+        let flushed_1 = accumulate_row(
+            &mut table,
+            &mut prev_columns,
+            columns.clone(),
+            vec!["1".into()],
+        );
+        let flushed_2 = accumulate_row(
+            &mut table,
+            &mut prev_columns,
+            columns.clone(),
+            vec!["2".into()],
+        );
+
+        assert!(flushed_1.is_none());
+        assert!(flushed_2.is_none());
+        assert_eq!(table.as_ref().unwrap().rows().len(), 2);
+    }
+
+    #[test]
+    fn accumulate_row_flushes_and_starts_a_new_table_on_a_column_change() {
+        let mut table: Option<Table> = None;
+        let mut prev_columns = vec![];
+
+        accumulate_row(
+            &mut table,
+            &mut prev_columns,
+            vec!["id".to_string()],
+            vec!["1".into()],
+        );
+        accumulate_row(
+            &mut table,
+            &mut prev_columns,
+            vec!["id".to_string()],
+            vec!["2".into()],
+        );
+
+        let flushed = accumulate_row(
+            &mut table,
+            &mut prev_columns,
+            vec!["other".to_string()],
+            vec!["x".into()],
+        )
+        .expect("column change should flush the previous table");
+
+        assert_eq!(flushed.header().columns(), &["id".to_string()]);
+        assert_eq!(flushed.rows().len(), 2);
+
+        // The new table (for "other") is already in progress, keyed by the
+        // new columns, not reset to empty.
+        assert_eq!(
+            table.as_ref().unwrap().header().columns(),
+            &["other".to_string()]
+        );
+        assert_eq!(table.as_ref().unwrap().rows().len(), 1);
+        assert_eq!(prev_columns, vec!["other".to_string()]);
+    }
+
+    #[test]
+    fn accumulate_row_simulated_chunking_splits_rows_into_multiple_tables() {
+        // Mirrors what `process_stream` does with its `chunk_rows`
+        // high-water mark: flush whenever the in-progress table reaches the
+        // configured size, without losing `prev_columns` across the flush.
+        const CHUNK_ROWS: usize = 2;
+
+        let mut table: Option<Table> = None;
+        let mut prev_columns = vec![];
+        let mut flushed_tables = vec![];
+
+        for value in ["1", "2", "3", "4", "5"] {
+            let flushed = accumulate_row(
+                &mut table,
+                &mut prev_columns,
+                vec!["id".to_string()],
+                vec![value.to_string()],
+            );
+
+            if let Some(flushed) = flushed {
+                flushed_tables.push(flushed);
+            } else if table.as_ref().unwrap().rows().len() >= CHUNK_ROWS {
+                flushed_tables.push(table.take().unwrap());
+            }
+        }
+        if let Some(table) = table.take() {
+            flushed_tables.push(table);
+        }
+
+        let chunk_sizes = flushed_tables
+            .iter()
+            .map(|t| t.rows().len())
+            .collect::<Vec<_>>();
+        assert_eq!(chunk_sizes, vec![2, 2, 1]);
+
+        let all_values = flushed_tables
+            .iter()
+            .flat_map(|t| t.rows().iter().map(|r| r.values()[0].clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(all_values, vec!["1", "2", "3", "4", "5"]);
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn as_pretty_right_aligns_numeric_columns_only_when_header_carries_types() {
+        let rows = vec![Row::new(vec!["100".into()]), Row::new(vec!["1".into()])];
+
+        let mut typed_header = Header::new(vec!["amount".into()]);
+        typed_header.set_types(vec![Type::INT4]);
+        let typed_output = Table::new(typed_header, rows.clone())
+            .as_pretty()
+            .to_string();
+
+        // This is what `process_batches` builds: no call to `set_types`, so
+        // `Header::types` stays empty and `as_pretty` has nothing to align by.
+        let untyped_output = Table::new(Header::new(vec!["amount".into()]), rows)
+            .as_pretty()
+            .to_string();
+
+        let digit_lines = |output: &str| {
+            output
+                .lines()
+                .filter(|line| line.chars().any(|c| c.is_ascii_digit()))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        // Row order is [ "100", "1" ]; the second line is the short value,
+        // whose leading-space count reveals the alignment that was applied.
+        let typed_one_line = &digit_lines(&typed_output)[1];
+        let untyped_one_line = &digit_lines(&untyped_output)[1];
+
+        let typed_pos = typed_one_line.find('1').unwrap();
+        let untyped_pos = untyped_one_line.find('1').unwrap();
+
+        assert!(
+            typed_pos > untyped_pos,
+            "typed column should right-align \"1\" further right than the untyped column: typed={typed_one_line:?} untyped={untyped_one_line:?}"
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn with_null_sentinel_rewrites_already_stored_null_cells() {
+        let header = Header::new(vec!["value".into()]);
+        let rows = vec![Row::new(vec![NULL_SENTINEL.to_string()])];
+
+        let table = Table::new(header, rows).with_null_sentinel("NULL");
+        let rendered = table.as_pretty().to_string();
+
+        assert!(rendered.contains("NULL"));
+        assert!(!rendered.contains(NULL_SENTINEL));
+    }
+}